@@ -0,0 +1,136 @@
+use crate::config::Config;
+use crate::ddc_guard;
+use crate::identity::display_id;
+use crate::spline::MonotoneCubicSpline;
+use ddc_hi::{Ddc, Display};
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+// Default lux -> brightness control points, used when the config file
+// doesn't set `auto.points`.
+const DEFAULT_POINTS: &[(f64, f64)] = &[
+    (0.0, 5.0),
+    (10.0, 15.0),
+    (50.0, 35.0),
+    (200.0, 55.0),
+    (1000.0, 80.0),
+    (10000.0, 100.0),
+];
+
+// Ignore lux->brightness deltas smaller than this; otherwise minor
+// sensor noise would keep nudging the displays up and down forever.
+const CHANGE_THRESHOLD: f64 = 2.0;
+
+const SLOW_POLL: Duration = Duration::from_millis(2000);
+const FAST_POLL: Duration = Duration::from_millis(100);
+const RAMP_STEPS: i16 = 10;
+const RAMP_STEP_DELAY: Duration = Duration::from_millis(30);
+
+// Read the current illuminance in lux from a sysfs iio sensor.
+fn read_lux(sensor_path: &str) -> Option<f64> {
+    fs::read_to_string(sensor_path)
+        .ok()
+        .and_then(|s| s.trim().parse::<f64>().ok())
+}
+
+// Find the first `in_illuminance_raw` sensor under
+// /sys/bus/iio/devices, if one exists.
+fn default_sensor_path() -> Option<String> {
+    let entries = fs::read_dir("/sys/bus/iio/devices").ok()?;
+    for entry in entries.flatten() {
+        let candidate = entry.path().join("in_illuminance_raw");
+        if candidate.exists() {
+            return Some(candidate.to_string_lossy().to_string());
+        }
+    }
+    None
+}
+
+// Ramp every DDC display from its current brightness toward `target`
+// in a handful of steps, so the change doesn't look like an abrupt
+// jump on screen.
+fn ramp_to(target: u16) {
+    let mut displays = Display::enumerate();
+    for display in &mut displays {
+        let id = display_id(&display.info);
+
+        let current = match ddc_guard::with_retry(&id, || display.handle.get_vcp_feature(0x10)) {
+            Ok(value) => value.value(),
+            Err(_) => continue,
+        };
+
+        let delta = target as i16 - current as i16;
+        if delta == 0 {
+            continue;
+        }
+
+        for step in 1..=RAMP_STEPS {
+            let intermediate =
+                (current as i16 + delta * step / RAMP_STEPS).clamp(0, 100) as u16;
+            let result = ddc_guard::with_retry(&id, || {
+                display.handle.set_vcp_feature(0x10, intermediate)
+            });
+            if result.is_err() {
+                break;
+            }
+            thread::sleep(RAMP_STEP_DELAY);
+        }
+    }
+}
+
+// Run the ambient-light auto-brightness daemon. Never returns under
+// normal operation.
+pub fn run(sensor_path: Option<String>) -> ! {
+    let sensor_path = sensor_path.or_else(default_sensor_path).unwrap_or_else(|| {
+        eprintln!("No illuminance sensor found; pass one explicitly with --sensor <path>.");
+        std::process::exit(1);
+    });
+
+    // MonotoneCubicSpline::new requires at least two control points;
+    // a hand-edited config could set `auto.points` to anything valid
+    // JSON allows (including too few), so fall back to the built-in
+    // table rather than trusting that invariant against user input.
+    let points = Config::load_or_create()
+        .auto
+        .map(|auto| auto.points)
+        .filter(|points| {
+            if points.len() < 2 {
+                eprintln!(
+                    "Ignoring auto.points in config: need at least 2 control points, found {}.",
+                    points.len()
+                );
+                false
+            } else {
+                true
+            }
+        })
+        .unwrap_or_else(|| DEFAULT_POINTS.to_vec());
+    let spline = MonotoneCubicSpline::new(points);
+
+    let mut last_target: Option<f64> = None;
+    let mut poll_interval = SLOW_POLL;
+
+    loop {
+        match read_lux(&sensor_path) {
+            Some(lux) => {
+                let target = spline.eval(lux).clamp(0.0, 100.0);
+                let changed = match last_target {
+                    Some(prev) => (target - prev).abs() >= CHANGE_THRESHOLD,
+                    None => true,
+                };
+
+                if changed {
+                    ramp_to(target.round() as u16);
+                    last_target = Some(target);
+                    poll_interval = FAST_POLL;
+                } else {
+                    poll_interval = SLOW_POLL;
+                }
+            }
+            None => eprintln!("Failed to read illuminance sensor at {}", sensor_path),
+        }
+
+        thread::sleep(poll_interval);
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+// What we remember about a single display between runs: whether it
+// answers VCP 0x10 at all, and its last known value/max so a
+// following set/adjust can skip re-probing.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CacheEntry {
+    pub supported: bool,
+    pub value: Option<u16>,
+    pub max: Option<u16>,
+}
+
+// A persistent, per-display probe cache, keyed by `identity::display_id`.
+//
+// Plain JSON under the user cache dir rather than a database: the
+// whole thing is a handful of entries, read and rewritten wholesale
+// on every invocation, so there's nothing a database would buy us.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Cache {
+    displays: HashMap<String, CacheEntry>,
+
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Cache {
+    fn cache_path() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("brightshift")
+            .join("displays.json")
+    }
+
+    // Load the cache from disk, or start empty if it doesn't exist
+    // yet or is unreadable/corrupt.
+    pub fn load() -> Cache {
+        let path = Self::cache_path();
+        let mut cache = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Cache>(&contents).ok())
+            .unwrap_or_default();
+        cache.path = path;
+        cache
+    }
+
+    // Start empty regardless of what's on disk, used for --refresh.
+    pub fn empty() -> Cache {
+        Cache {
+            displays: HashMap::new(),
+            path: Self::cache_path(),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&CacheEntry> {
+        self.displays.get(id)
+    }
+
+    pub fn set(&mut self, id: &str, entry: CacheEntry) {
+        self.displays.insert(id.to_string(), entry);
+    }
+
+    pub fn save(&self) {
+        let Some(parent) = self.path.parent() else {
+            return;
+        };
+        if fs::create_dir_all(parent).is_err() {
+            return;
+        }
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = fs::write(&self.path, json);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+// Per-display overrides, keyed by `identity::display_id` for DDC/CI
+// displays and by the device name for sysfs backlight devices.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct DisplayConfig {
+    // A short, memorable name usable with --display instead of the
+    // full model_name/serial id or a numeric --list index.
+    pub alias: Option<String>,
+    // Brightness to apply when this display is targeted without an
+    // explicit value, e.g. `brightshift --display bedroom`.
+    pub default_brightness: Option<u16>,
+    pub min: Option<u16>,
+    pub max: Option<u16>,
+}
+
+// The lux -> brightness control points used by `--auto` (see
+// spline::MonotoneCubicSpline). Stored as plain (lux, brightness)
+// pairs so the config file can express the same table the built-in
+// default uses.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AutoConfig {
+    pub points: Vec<(f64, f64)>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Config {
+    pub displays: HashMap<String, DisplayConfig>,
+    pub auto: Option<AutoConfig>,
+}
+
+impl Config {
+    fn config_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("brightshift")
+            .join("config.json")
+    }
+
+    // Load the config, creating an empty one on disk the first time
+    // brightshift runs so users have a file to edit. A config that
+    // exists but fails to parse (e.g. a typo from hand-editing) is
+    // reported and ignored for this run, but never overwritten --
+    // `save()` only runs when there was no file to lose in the first
+    // place.
+    pub fn load_or_create() -> Config {
+        let path = Self::config_path();
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(config) => config,
+                Err(err) => {
+                    eprintln!(
+                        "Failed to parse config at {:?}, using defaults for this run: {}",
+                        path, err
+                    );
+                    Config::default()
+                }
+            },
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                let config = Config::default();
+                config.save();
+                config
+            }
+            Err(err) => {
+                eprintln!(
+                    "Failed to read config at {:?}, using defaults for this run: {}",
+                    path, err
+                );
+                Config::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let path = Self::config_path();
+        let Some(parent) = path.parent() else {
+            return;
+        };
+        if fs::create_dir_all(parent).is_err() {
+            return;
+        }
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = fs::write(&path, json);
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&DisplayConfig> {
+        self.displays.get(id)
+    }
+
+    // Resolve a --display selector against a display's id: by its
+    // stable id directly, or by the alias configured for that id.
+    pub fn selector_matches(&self, selector: &str, id: &str) -> bool {
+        if selector == id {
+            return true;
+        }
+        match self.get(id).and_then(|d| d.alias.as_deref()) {
+            Some(alias) => alias == selector,
+            None => false,
+        }
+    }
+
+    // Clamp `value` to the [min, max] configured for this display, if
+    // any bound is set.
+    pub fn clamp(&self, id: &str, value: u16) -> u16 {
+        match self.get(id) {
+            Some(entry) => {
+                let min = entry.min.unwrap_or(0);
+                let max = entry.max.unwrap_or(100);
+                value.clamp(min, max)
+            }
+            None => value,
+        }
+    }
+}
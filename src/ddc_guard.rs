@@ -0,0 +1,68 @@
+use fs2::FileExt;
+use std::fs::{self, File};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+// DDC/CI rides on I2C, which can't handle more than one in-flight
+// query per bus. Two brightshift invocations running at once (e.g.
+// two hotkey presses in quick succession) would otherwise race on the
+// same bus and both come back with spurious errors.
+//
+// This process-wide lock serializes every VCP call within a single
+// brightshift process; the per-display file lock below additionally
+// covers the case of two separate processes running concurrently.
+static GLOBAL_DDC_LOCK: Mutex<()> = Mutex::new(());
+
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_BACKOFF: Duration = Duration::from_millis(150);
+
+// An advisory file lock held for the duration of a VCP call, so two
+// separate brightshift processes don't talk to the same display's
+// I2C bus at once.
+struct DisplayFileLock {
+    file: File,
+}
+
+impl DisplayFileLock {
+    fn acquire(id: &str) -> Option<DisplayFileLock> {
+        let dir = std::env::temp_dir().join("brightshift-locks");
+        fs::create_dir_all(&dir).ok()?;
+        let path = dir.join(format!("{}.lock", id));
+        let file = File::create(path).ok()?;
+        file.lock_exclusive().ok()?;
+        Some(DisplayFileLock { file })
+    }
+}
+
+impl Drop for DisplayFileLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+// Run `op` against a single display's VCP interface, serialized
+// against every other brightshift call in this process (and, via the
+// file lock, other processes' calls to the same display) and retried a bounded number of
+// times with a short backoff, since transient DDC errors are common.
+pub fn with_retry<T>(
+    id: &str,
+    mut op: impl FnMut() -> Result<T, ddc_hi::Error>,
+) -> Result<T, ddc_hi::Error> {
+    let _process_guard = GLOBAL_DDC_LOCK.lock().unwrap();
+    let _file_guard = DisplayFileLock::acquire(id);
+
+    let mut last_err = None;
+    for attempt in 0..MAX_ATTEMPTS {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                last_err = Some(err);
+                if attempt + 1 < MAX_ATTEMPTS {
+                    thread::sleep(RETRY_BACKOFF);
+                }
+            }
+        }
+    }
+    Err(last_err.unwrap())
+}
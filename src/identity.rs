@@ -0,0 +1,15 @@
+// Build a stable, topic/filename-safe identity for a display out of
+// its model name and serial number, e.g. "DELL_U2720Q_ABC123".
+//
+// Shared by the MQTT bridge (topic naming), the probe cache (cache
+// keys), the config file (per-display overrides), and the file lock
+// (lock names), so two displays must never produce the same id. When
+// ddc_hi can't read a serial number, fall back to its own stable
+// per-display identifier (its DDC/CI bus path) rather than a constant
+// string -- a constant would make every such display collide on the
+// same id and silently share state with each other.
+pub fn display_id(info: &ddc_hi::DisplayInfo) -> String {
+    let model = info.model_name.clone().unwrap_or_else(|| "unknown".into());
+    let serial = info.serial_number.clone().unwrap_or_else(|| info.id.clone());
+    format!("{}_{}", model, serial).replace([' ', '/'], "_")
+}
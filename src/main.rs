@@ -1,6 +1,69 @@
+mod auto;
+mod cache;
+mod config;
+mod ddc_guard;
+mod identity;
+mod mqtt;
+mod report;
+mod spline;
+mod sysfs;
+
+use cache::{Cache, CacheEntry};
+use config::Config;
 use ddc_hi::{Ddc, Display};
+use identity::display_id;
+use report::DisplayReport;
 use std::env;
 use std::process::exit;
+use sysfs::SysfsDevice;
+
+// Whether the display/device at `index` with identity `id` matches a
+// --display selector: by its combined --list index, by its stable
+// id, or by the alias configured for that id. No selector means
+// everything matches (the default broadcast-to-all behavior).
+fn target_selected(selector: &Option<String>, index: usize, id: &str, config: &Config) -> bool {
+    match selector {
+        None => true,
+        Some(sel) => {
+            sel.parse::<usize>().map(|i| i == index).unwrap_or(false)
+                || config.selector_matches(sel, id)
+        }
+    }
+}
+
+// Resolve a --display selector (--list index, id, or alias) to the
+// stable id of the one display/device it names, using the same
+// numbering `target_selected` and --list use: ddc displays first,
+// then sysfs devices.
+fn resolve_selector_id(
+    selector: &str,
+    config: &Config,
+    use_ddc: bool,
+    use_sysfs: bool,
+) -> Option<String> {
+    let selector = Some(selector.to_string());
+    let mut index = 0;
+
+    if use_ddc {
+        for display in Display::enumerate() {
+            let id = display_id(&display.info);
+            if target_selected(&selector, index, &id, config) {
+                return Some(id);
+            }
+            index += 1;
+        }
+    }
+    if !use_sysfs {
+        return None;
+    }
+    for device in SysfsDevice::enumerate() {
+        if target_selected(&selector, index, &device.name, config) {
+            return Some(device.name);
+        }
+        index += 1;
+    }
+    None
+}
 
 // Print usage information for the program.
 fn print_usage(program_name: &str) {
@@ -22,6 +85,42 @@ fn print_usage(program_name: &str) {
         "  {} +/-<number>     Adjust brightness by the specified value (0-100)",
         program_name
     );
+    println!(
+        "  {} --no-ddc        Skip external DDC/CI displays",
+        program_name
+    );
+    println!(
+        "  {} --no-sysfs      Skip internal sysfs backlight devices",
+        program_name
+    );
+    println!(
+        "  {} --auto           Run an ambient-light auto-brightness daemon",
+        program_name
+    );
+    println!(
+        "  {} --sensor <path>  Illuminance sensor to use with --auto",
+        program_name
+    );
+    println!(
+        "  {} --mqtt <broker-url>  Run an MQTT bridge for home-automation control",
+        program_name
+    );
+    println!(
+        "  {} --refresh       Ignore the probe cache and re-probe every display",
+        program_name
+    );
+    println!(
+        "  {} --display <name-or-index>  Only target one display/device",
+        program_name
+    );
+    println!(
+        "  {} --list           List enumerated displays/devices with their --display indices",
+        program_name
+    );
+    println!(
+        "  {} --json           Print --get/--status output as machine-readable JSON",
+        program_name
+    );
 }
 
 fn main() {
@@ -49,16 +148,60 @@ fn main() {
     let mut print_status = false;
     let mut get_brightness = false;
     let mut adjust_brightness: Option<i16> = None;
+    let mut use_ddc = true;
+    let mut use_sysfs = true;
+    let mut run_auto = false;
+    let mut sensor_path: Option<String> = None;
+    let mut mqtt_broker: Option<String> = None;
+    let mut refresh_cache = false;
+    let mut display_selector: Option<String> = None;
+    let mut print_list = false;
+    let mut json_output = false;
 
     // Parse arguments
-    for arg in &args[1..] {
-        match arg.as_str() {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        match arg {
             "--help" => print_help = true,
             "-h" => print_help = true,
             "--status" => print_status = true,
             "-s" => print_status = true,
             "--get" => get_brightness = true,
             "-g" => get_brightness = true,
+            "--no-ddc" => use_ddc = false,
+            "--no-sysfs" => use_sysfs = false,
+            "--refresh" => refresh_cache = true,
+            "--list" => print_list = true,
+            "--json" => json_output = true,
+            "--display" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("Error: --display requires a name or index argument.");
+                    print_usage(&args[0]);
+                    exit(1);
+                }
+                display_selector = Some(args[i].clone());
+            }
+            "--auto" => run_auto = true,
+            "--sensor" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("Error: --sensor requires a path argument.");
+                    print_usage(&args[0]);
+                    exit(1);
+                }
+                sensor_path = Some(args[i].clone());
+            }
+            "--mqtt" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("Error: --mqtt requires a broker URL argument.");
+                    print_usage(&args[0]);
+                    exit(1);
+                }
+                mqtt_broker = Some(args[i].clone());
+            }
             value if value.starts_with('+') || value.starts_with('-') => {
                 if let Ok(delta) = value.parse::<i16>() {
                     adjust_brightness = Some(delta);
@@ -77,6 +220,7 @@ fn main() {
                 brightness_value = Some(value.to_string());
             }
         }
+        i += 1;
     }
 
     if print_help {
@@ -84,75 +228,402 @@ fn main() {
         exit(0);
     }
 
+    if !use_ddc && !use_sysfs {
+        eprintln!("Error: --no-ddc and --no-sysfs can't both be set, nothing to do.");
+        exit(1);
+    }
+
+    if run_auto {
+        auto::run(sensor_path);
+    }
+
+    if let Some(broker_url) = mqtt_broker {
+        mqtt::run(&broker_url);
+    }
+
+    let config = Config::load_or_create();
+
+    if print_list {
+        // Honor --no-ddc/--no-sysfs here too, so the indices this
+        // prints stay the same ones --display and every other command
+        // use when a backend is excluded (see the ddc_count comment
+        // below).
+        let mut index = 0;
+        if use_ddc {
+            for mut display in Display::enumerate() {
+                let id = display_id(&display.info);
+                let alias = config.get(&id).and_then(|d| d.alias.clone());
+                match alias {
+                    Some(alias) => println!(
+                        "[{}] ddc   {:?} (id={}, alias={})",
+                        index, display.info.model_name, id, alias
+                    ),
+                    None => println!(
+                        "[{}] ddc   {:?} (id={})",
+                        index, display.info.model_name, id
+                    ),
+                }
+                index += 1;
+            }
+        }
+        if use_sysfs {
+            for device in SysfsDevice::enumerate() {
+                let alias = config.get(&device.name).and_then(|d| d.alias.clone());
+                match alias {
+                    Some(alias) => println!(
+                        "[{}] sysfs {:?} (id={}, alias={})",
+                        index, device.name, device.name, alias
+                    ),
+                    None => println!("[{}] sysfs {:?} (id={})", index, device.name, device.name),
+                }
+                index += 1;
+            }
+        }
+        exit(0);
+    }
+
     if print_status {
-        // Check if displays support brightness adjustment via DDC/CI
-        let displays = Display::enumerate();
-        for mut display in displays {
-            match display.handle.get_vcp_feature(0x10) {
-                Ok(_) => println!(
-                    "Display {:?} supports brightness adjustment via DDC/CI.",
-                    display.info.model_name
-                ),
-                Err(_) => println!(
-                    "Display {:?} does not support brightness adjustment via DDC/CI.",
-                    display.info.model_name
-                ),
+        // --display indices number ddc displays first, then sysfs
+        // devices, matching --list's ordering under the same
+        // --no-ddc/--no-sysfs flags (with --no-ddc, sysfs devices
+        // start at index 0). Only probe DDC when
+        // it's actually in use -- Display::enumerate() does real bus
+        // I/O, which --no-ddc promises to avoid -- and enumerate just
+        // once, reusing it both for the count and the loop below.
+        let mut displays = if use_ddc { Display::enumerate() } else { Vec::new() };
+        let ddc_count = displays.len();
+        let mut reports = Vec::new();
+
+        if use_ddc {
+            if !json_output {
+                println!("DDC/CI displays:");
+            }
+            let mut cache = if refresh_cache {
+                Cache::empty()
+            } else {
+                Cache::load()
+            };
+            for (index, display) in displays.iter_mut().enumerate() {
+                let id = display_id(&display.info);
+                if !target_selected(&display_selector, index, &id, &config) {
+                    continue;
+                }
+                let name = format!("{:?}", display.info.model_name);
+
+                if let Some(entry) = cache.get(&id) {
+                    if !entry.supported {
+                        if !json_output {
+                            println!(
+                                "  {} does not support brightness adjustment via DDC/CI. (cached)",
+                                name
+                            );
+                        }
+                        reports.push(DisplayReport {
+                            name,
+                            backend: "ddc",
+                            supported: false,
+                            value: None,
+                            max: None,
+                        });
+                        continue;
+                    }
+                }
+
+                match ddc_guard::with_retry(&id, || display.handle.get_vcp_feature(0x10)) {
+                    Ok(value) => {
+                        if !json_output {
+                            println!("  {} supports brightness adjustment via DDC/CI.", name);
+                        }
+                        cache.set(
+                            &id,
+                            CacheEntry {
+                                supported: true,
+                                value: Some(value.value()),
+                                max: Some(value.maximum()),
+                            },
+                        );
+                        reports.push(DisplayReport {
+                            name,
+                            backend: "ddc",
+                            supported: true,
+                            value: Some(report::to_percent(value.value(), value.maximum())),
+                            max: Some(100),
+                        });
+                    }
+                    Err(_) => {
+                        if !json_output {
+                            println!(
+                                "  {} does not support brightness adjustment via DDC/CI.",
+                                name
+                            );
+                        }
+                        cache.set(
+                            &id,
+                            CacheEntry {
+                                supported: false,
+                                value: None,
+                                max: None,
+                            },
+                        );
+                        reports.push(DisplayReport {
+                            name,
+                            backend: "ddc",
+                            supported: false,
+                            value: None,
+                            max: None,
+                        });
+                    }
+                }
+            }
+            cache.save();
+        }
+        if use_sysfs {
+            if !json_output {
+                println!("Sysfs backlight devices:");
             }
+            for (offset, device) in SysfsDevice::enumerate().into_iter().enumerate() {
+                let index = ddc_count + offset;
+                if !target_selected(&display_selector, index, &device.name, &config) {
+                    continue;
+                }
+                if !json_output {
+                    println!(
+                        "  {:?} supports brightness adjustment via sysfs (max_brightness={}).",
+                        device.name, device.max_brightness
+                    );
+                }
+                reports.push(DisplayReport {
+                    name: device.name.clone(),
+                    backend: "sysfs",
+                    supported: true,
+                    value: device.get_percent().ok(),
+                    max: Some(100),
+                });
+            }
+        }
+
+        if json_output {
+            report::print_json(&reports);
         }
         exit(0);
     }
 
     if get_brightness {
-        // Retrieve and print the current brightness level
-        let displays = Display::enumerate();
-        for mut display in displays {
-            match display.handle.get_vcp_feature(0x10) {
-                Ok(value) => println!("{}", value.value()),
-                Err(_) => println!(
-                    "Failed to get brightness for display {:?}",
-                    display.info.model_name
-                ),
+        // Retrieve and print the current brightness level. Skip the
+        // DDC probe entirely when it's excluded, and enumerate just
+        // once, reusing it both for the count and the loop below --
+        // enumerate() does real bus I/O, which --no-ddc promises to
+        // avoid and which repeated calls would otherwise duplicate.
+        let mut displays = if use_ddc { Display::enumerate() } else { Vec::new() };
+        let ddc_count = displays.len();
+        let mut reports = Vec::new();
+
+        if use_ddc {
+            for (index, display) in displays.iter_mut().enumerate() {
+                let id = display_id(&display.info);
+                if !target_selected(&display_selector, index, &id, &config) {
+                    continue;
+                }
+                let name = format!("{:?}", display.info.model_name);
+                match ddc_guard::with_retry(&id, || display.handle.get_vcp_feature(0x10)) {
+                    Ok(value) => {
+                        if !json_output {
+                            println!("{}", value.value());
+                        }
+                        reports.push(DisplayReport {
+                            name,
+                            backend: "ddc",
+                            supported: true,
+                            value: Some(report::to_percent(value.value(), value.maximum())),
+                            max: Some(100),
+                        });
+                    }
+                    Err(_) => {
+                        if !json_output {
+                            println!("Failed to get brightness for display {}", name);
+                        }
+                        reports.push(DisplayReport {
+                            name,
+                            backend: "ddc",
+                            supported: false,
+                            value: None,
+                            max: None,
+                        });
+                    }
+                }
+            }
+        }
+        if use_sysfs {
+            for (offset, device) in SysfsDevice::enumerate().into_iter().enumerate() {
+                let index = ddc_count + offset;
+                if !target_selected(&display_selector, index, &device.name, &config) {
+                    continue;
+                }
+                match device.get_percent() {
+                    Ok(pct) => {
+                        if !json_output {
+                            println!("{}", pct);
+                        }
+                        reports.push(DisplayReport {
+                            name: device.name.clone(),
+                            backend: "sysfs",
+                            supported: true,
+                            value: Some(pct),
+                            max: Some(100),
+                        });
+                    }
+                    Err(_) => {
+                        if !json_output {
+                            println!("Failed to get brightness for device {:?}", device.name);
+                        }
+                        reports.push(DisplayReport {
+                            name: device.name.clone(),
+                            backend: "sysfs",
+                            supported: false,
+                            value: None,
+                            max: None,
+                        });
+                    }
+                }
             }
         }
+
+        if json_output {
+            report::print_json(&reports);
+        }
         exit(0);
     }
 
     if let Some(delta) = adjust_brightness {
-        // Adjust brightness by the specified delta value
-        let mut displays = Display::enumerate();
-        for display in &mut displays {
-            match display.handle.get_vcp_feature(0x10) {
-                Ok(current_value) => {
-                    let new_brightness =
-                        (current_value.value() as i16 + delta).clamp(0, 100) as u16;
-                    match display.handle.set_vcp_feature(0x10, new_brightness) {
-                        Ok(_) => println!(
+        // Adjust brightness by the specified delta value. Skip the DDC
+        // probe entirely when it's excluded, and enumerate just once,
+        // reusing it both for the count and the loop below --
+        // enumerate() does real bus I/O, which --no-ddc promises to
+        // avoid and which repeated calls would otherwise duplicate.
+        let mut displays = if use_ddc { Display::enumerate() } else { Vec::new() };
+        let ddc_count = displays.len();
+
+        if use_ddc {
+            let mut cache = if refresh_cache {
+                Cache::empty()
+            } else {
+                Cache::load()
+            };
+            for (index, display) in displays.iter_mut().enumerate() {
+                let id = display_id(&display.info);
+                if !target_selected(&display_selector, index, &id, &config) {
+                    continue;
+                }
+
+                // A cached value lets us skip the round-trip read and
+                // go straight to computing the new target.
+                let cached = cache.get(&id).filter(|e| e.supported).cloned();
+                let current = match &cached {
+                    Some(entry) => entry.value,
+                    None => {
+                        match ddc_guard::with_retry(&id, || display.handle.get_vcp_feature(0x10)) {
+                            Ok(value) => Some(value.value()),
+                            Err(_) => None,
+                        }
+                    }
+                };
+
+                let Some(current) = current else {
+                    eprintln!(
+                        "Failed to get current brightness for display {:?}",
+                        display.info.model_name
+                    );
+                    continue;
+                };
+
+                // A probed max clamps accurately; without one, 100 is
+                // just a clamp ceiling for this call and must not be
+                // written back as if it were hardware-verified.
+                let known_max = cached.as_ref().and_then(|e| e.max);
+                let clamp_ceiling = known_max.unwrap_or(100);
+                let new_brightness = config
+                    .clamp(&id, (current as i16 + delta).clamp(0, clamp_ceiling as i16) as u16);
+                match ddc_guard::with_retry(&id, || {
+                    display.handle.set_vcp_feature(0x10, new_brightness)
+                }) {
+                    Ok(_) => {
+                        println!(
                             "Brightness adjusted to {} on display {:?}",
                             new_brightness, display.info.model_name
-                        ),
-                        Err(err) => eprintln!(
-                            "Failed to set brightness on display {:?}: {:?}",
-                            display.info.model_name, err
-                        ),
+                        );
+                        cache.set(
+                            &id,
+                            CacheEntry {
+                                supported: true,
+                                value: Some(new_brightness),
+                                max: known_max,
+                            },
+                        );
                     }
+                    Err(err) => eprintln!(
+                        "Failed to set brightness on display {:?}: {:?}",
+                        display.info.model_name, err
+                    ),
+                }
+            }
+            cache.save();
+        }
+        if use_sysfs {
+            for (offset, device) in SysfsDevice::enumerate().into_iter().enumerate() {
+                let index = ddc_count + offset;
+                if !target_selected(&display_selector, index, &device.name, &config) {
+                    continue;
+                }
+                match device.get_percent() {
+                    Ok(current_pct) => {
+                        let new_pct = config
+                            .clamp(&device.name, (current_pct as i16 + delta).clamp(0, 100) as u16);
+                        match device.set_percent(new_pct) {
+                            Ok(_) => println!(
+                                "Brightness adjusted to {} on device {:?}",
+                                new_pct, device.name
+                            ),
+                            Err(err) => eprintln!(
+                                "Failed to set brightness on device {:?}: {:?}",
+                                device.name, err
+                            ),
+                        }
+                    }
+                    Err(_) => eprintln!(
+                        "Failed to get current brightness for device {:?}",
+                        device.name
+                    ),
                 }
-                Err(_) => eprintln!(
-                    "Failed to get current brightness for display {:?}",
-                    display.info.model_name
-                ),
             }
         }
         exit(0);
     }
 
+    // If no explicit value was given, fall back to the configured
+    // default_brightness for the targeted display, if any. Resolve
+    // the selector to an id first so a numeric --list index (not
+    // just an id or alias) finds its default too.
+    if brightness_value.is_none() {
+        if let Some(selector) = &display_selector {
+            if let Some(id) = resolve_selector_id(selector, &config, use_ddc, use_sysfs) {
+                if let Some(default) = config.get(&id).and_then(|e| e.default_brightness) {
+                    brightness_value = Some(default.to_string());
+                }
+            }
+        }
+    }
+
+    let Some(brightness_value) = brightness_value else {
+        eprintln!("No brightness value specified and no configured default for this display.");
+        print_usage(&args[0]);
+        exit(1);
+    };
+
     // If neither --help nor --status nor --get was specified, handle brightness adjustment
-    let new_brightness: u16 = match brightness_value.as_ref().unwrap().parse() {
+    let new_brightness: u16 = match brightness_value.parse() {
         Ok(value) => value,
         Err(_) => {
-            eprintln!(
-                "Invalid brightness value: {}",
-                brightness_value.as_ref().unwrap()
-            );
+            eprintln!("Invalid brightness value: {}", brightness_value);
             exit(1);
         }
     };
@@ -163,26 +634,80 @@ fn main() {
         exit(1);
     }
 
+    let mut found_any = false;
+    // Skip the DDC probe entirely when it's excluded, and enumerate
+    // just once, reusing it both for the count and the loop below --
+    // enumerate() does real bus I/O, which --no-ddc promises to avoid
+    // and which repeated calls would otherwise duplicate.
+    let mut displays = if use_ddc { Display::enumerate() } else { Vec::new() };
+    let ddc_count = displays.len();
+
     // Retrieve all connected displays that support DDC/CI
-    let mut displays = Display::enumerate();
+    if use_ddc {
+        let mut cache = if refresh_cache {
+            Cache::empty()
+        } else {
+            Cache::load()
+        };
+        for (index, display) in displays.iter_mut().enumerate() {
+            let id = display_id(&display.info);
+            if !target_selected(&display_selector, index, &id, &config) {
+                continue;
+            }
+            found_any = true;
 
-    if displays.is_empty() {
-        eprintln!("No displays supporting DDC/CI found.");
-        exit(1);
+            // Preserve whatever max was actually probed, if any;
+            // never fabricate one just to have something to cache.
+            let known_max = cache.get(&id).filter(|e| e.supported).and_then(|e| e.max);
+            let target = config.clamp(&id, new_brightness);
+            match ddc_guard::with_retry(&id, || display.handle.set_vcp_feature(0x10, target)) {
+                Ok(_) => {
+                    println!(
+                        "Brightness set to {} on display {:?}",
+                        target, display.info.model_name
+                    );
+                    cache.set(
+                        &id,
+                        CacheEntry {
+                            supported: true,
+                            value: Some(target),
+                            max: known_max,
+                        },
+                    );
+                }
+                Err(err) => eprintln!(
+                    "Failed to set brightness on display {:?}: {:?}",
+                    display.info.model_name, err
+                ),
+            }
+        }
+        cache.save();
     }
 
-    // Iterate through each display and set the brightness
-    for display in &mut displays {
-        match display.handle.set_vcp_feature(0x10, new_brightness) {
-            Ok(_) => println!(
-                "Brightness set to {} on display {:?}",
-                new_brightness, display.info.model_name
-            ),
-            Err(err) => eprintln!(
-                "Failed to set brightness on display {:?}: {:?}",
-                display.info.model_name, err
-            ),
+    if use_sysfs {
+        for (offset, device) in SysfsDevice::enumerate().into_iter().enumerate() {
+            let index = ddc_count + offset;
+            if !target_selected(&display_selector, index, &device.name, &config) {
+                continue;
+            }
+            found_any = true;
+
+            let target = config.clamp(&device.name, new_brightness);
+            match device.set_percent(target) {
+                Ok(_) => println!(
+                    "Brightness set to {} on device {:?}",
+                    target, device.name
+                ),
+                Err(err) => eprintln!(
+                    "Failed to set brightness on device {:?}: {:?}",
+                    device.name, err
+                ),
+            }
         }
     }
-}
 
+    if !found_any {
+        eprintln!("No displays supporting DDC/CI or sysfs backlight found.");
+        exit(1);
+    }
+}
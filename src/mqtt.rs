@@ -0,0 +1,119 @@
+use crate::ddc_guard;
+use crate::identity::display_id;
+use ddc_hi::{Ddc, Display};
+use rumqttc::{Client, Event, MqttOptions, Packet, QoS};
+use std::time::Duration;
+
+const SET_SUFFIX: &str = "/set";
+const STATE_SUFFIX: &str = "/state";
+const TOPIC_PREFIX: &str = "brightshift";
+
+fn set_topic(id: &str) -> String {
+    format!("{}/{}{}", TOPIC_PREFIX, id, SET_SUFFIX)
+}
+
+fn state_topic(id: &str) -> String {
+    format!("{}/{}{}", TOPIC_PREFIX, id, STATE_SUFFIX)
+}
+
+// Parse a `set` payload, which is either an absolute 0-100 value or a
+// signed delta like "+10"/"-5" resolved against `current`, exactly
+// like the existing +/-<number> adjust path on the command line.
+fn resolve_target(payload: &str, current: u16) -> Option<u16> {
+    let payload = payload.trim();
+    if payload.starts_with('+') || payload.starts_with('-') {
+        let delta = payload.parse::<i16>().ok()?;
+        Some((current as i16 + delta).clamp(0, 100) as u16)
+    } else {
+        payload.parse::<u16>().ok().map(|v| v.clamp(0, 100))
+    }
+}
+
+// Run the MQTT bridge: subscribe to a `set` topic per discovered
+// display and publish brightness changes to a `state` topic, so
+// brightshift can be driven by Home Assistant and friends.
+pub fn run(broker_url: &str) -> ! {
+    let mut options = MqttOptions::parse_url(broker_url.to_string())
+        .unwrap_or_else(|err| {
+            eprintln!("Invalid MQTT broker URL '{}': {:?}", broker_url, err);
+            std::process::exit(1);
+        });
+    options.set_keep_alive(Duration::from_secs(30));
+
+    let (client, mut connection) = Client::new(options, 10);
+
+    client
+        .subscribe(format!("{}/+{}", TOPIC_PREFIX, SET_SUFFIX), QoS::AtLeastOnce)
+        .expect("failed to subscribe to brightshift set topics");
+
+    // Publish the current brightness of every display once at
+    // startup so subscribers have an initial state to show.
+    for mut display in Display::enumerate() {
+        let id = display_id(&display.info);
+        if let Ok(value) = ddc_guard::with_retry(&id, || display.handle.get_vcp_feature(0x10)) {
+            let _ = client.publish(
+                state_topic(&id),
+                QoS::AtLeastOnce,
+                true,
+                value.value().to_string(),
+            );
+        }
+    }
+
+    for notification in connection.iter() {
+        let event = match notification {
+            Ok(event) => event,
+            Err(err) => {
+                eprintln!("MQTT connection error: {:?}", err);
+                continue;
+            }
+        };
+
+        let Event::Incoming(Packet::Publish(publish)) = event else {
+            continue;
+        };
+
+        let Some(id) = publish
+            .topic
+            .strip_prefix(&format!("{}/", TOPIC_PREFIX))
+            .and_then(|rest| rest.strip_suffix(SET_SUFFIX))
+        else {
+            continue;
+        };
+
+        let Ok(payload) = std::str::from_utf8(&publish.payload) else {
+            continue;
+        };
+
+        let mut displays = Display::enumerate();
+        let Some(display) = displays
+            .iter_mut()
+            .find(|d| display_id(&d.info) == id)
+        else {
+            eprintln!("No display found for MQTT id '{}'", id);
+            continue;
+        };
+
+        let current = match ddc_guard::with_retry(id, || display.handle.get_vcp_feature(0x10)) {
+            Ok(value) => value.value(),
+            Err(err) => {
+                eprintln!("Failed to read current brightness for '{}': {:?}", id, err);
+                continue;
+            }
+        };
+
+        let Some(target) = resolve_target(payload, current) else {
+            eprintln!("Invalid brightness payload '{}' for '{}'", payload, id);
+            continue;
+        };
+
+        match ddc_guard::with_retry(id, || display.handle.set_vcp_feature(0x10, target)) {
+            Ok(_) => {
+                let _ = client.publish(state_topic(id), QoS::AtLeastOnce, true, target.to_string());
+            }
+            Err(err) => eprintln!("Failed to set brightness for '{}': {:?}", id, err),
+        }
+    }
+
+    unreachable!("MQTT connection loop should never exit");
+}
@@ -0,0 +1,33 @@
+use serde::Serialize;
+
+// A single display/device's brightness info, shared by --status and
+// --get's --json output so status-bar scripts (waybar, i3status-rust)
+// get one stable shape to parse instead of scraping the text output.
+// `value` is always a 0-100 percentage and `max` is always 100,
+// regardless of backend, so a consumer never needs to know whether a
+// given row came from DDC/CI or sysfs to interpret it.
+#[derive(Serialize)]
+pub struct DisplayReport {
+    pub name: String,
+    pub backend: &'static str,
+    pub supported: bool,
+    pub value: Option<u16>,
+    pub max: Option<u16>,
+}
+
+// Rescale a raw `value` out of `max` to a 0-100 percentage, used to
+// normalize DDC/CI's native VCP units down to the same scale sysfs
+// already reports in.
+pub fn to_percent(value: u16, max: u16) -> u16 {
+    if max == 0 {
+        return 0;
+    }
+    ((value as u32 * 100) / max as u32) as u16
+}
+
+pub fn print_json(reports: &[DisplayReport]) {
+    match serde_json::to_string_pretty(reports) {
+        Ok(json) => println!("{}", json),
+        Err(err) => eprintln!("Failed to serialize JSON output: {:?}", err),
+    }
+}
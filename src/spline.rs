@@ -0,0 +1,93 @@
+// Monotone cubic interpolation (Fritsch-Carlson) over a small table of
+// (lux, brightness) control points.
+//
+// A plain cubic spline can overshoot between points, which would make the
+// auto-brightness curve dip or spike between control points even though
+// the table itself is monotonically increasing. The Fritsch-Carlson
+// correction clamps the tangents so the interpolated curve never
+// overshoots, which is what we want for a lux -> brightness mapping.
+pub struct MonotoneCubicSpline {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    tangents: Vec<f64>,
+}
+
+impl MonotoneCubicSpline {
+    // Build a spline from control points. Points are sorted by `x`
+    // (lux) before the tangents are computed; `points` must have at
+    // least two entries.
+    pub fn new(mut points: Vec<(f64, f64)>) -> Self {
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let xs: Vec<f64> = points.iter().map(|p| p.0).collect();
+        let ys: Vec<f64> = points.iter().map(|p| p.1).collect();
+        let n = xs.len();
+
+        // Secant slopes between consecutive points.
+        let mut secants = vec![0.0; n.saturating_sub(1)];
+        for i in 0..secants.len() {
+            secants[i] = (ys[i + 1] - ys[i]) / (xs[i + 1] - xs[i]);
+        }
+
+        // Initial tangents: average of neighbouring secants, falling
+        // back to the single adjacent secant at the ends.
+        let mut tangents = vec![0.0; n];
+        for i in 0..n {
+            tangents[i] = if i == 0 {
+                secants.first().copied().unwrap_or(0.0)
+            } else if i == n - 1 {
+                secants.last().copied().unwrap_or(0.0)
+            } else {
+                (secants[i - 1] + secants[i]) / 2.0
+            };
+        }
+
+        // Clamp tangents so the curve stays monotone between every pair
+        // of points (the Fritsch-Carlson step).
+        for i in 0..secants.len() {
+            if secants[i] == 0.0 {
+                tangents[i] = 0.0;
+                tangents[i + 1] = 0.0;
+                continue;
+            }
+            let a = tangents[i] / secants[i];
+            let b = tangents[i + 1] / secants[i];
+            let magnitude = (a * a + b * b).sqrt();
+            if magnitude > 3.0 {
+                let scale = 3.0 / magnitude;
+                tangents[i] = scale * a * secants[i];
+                tangents[i + 1] = scale * b * secants[i];
+            }
+        }
+
+        MonotoneCubicSpline { xs, ys, tangents }
+    }
+
+    // Evaluate the spline at `x`, clamping to the first/last control
+    // point outside the table's range rather than extrapolating.
+    pub fn eval(&self, x: f64) -> f64 {
+        let n = self.xs.len();
+        if x <= self.xs[0] {
+            return self.ys[0];
+        }
+        if x >= self.xs[n - 1] {
+            return self.ys[n - 1];
+        }
+
+        let i = self.xs.partition_point(|&xi| xi <= x) - 1;
+        let h = self.xs[i + 1] - self.xs[i];
+        let t = (x - self.xs[i]) / h;
+
+        let t2 = t * t;
+        let t3 = t2 * t;
+        let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+        let h10 = t3 - 2.0 * t2 + t;
+        let h01 = -2.0 * t3 + 3.0 * t2;
+        let h11 = t3 - t2;
+
+        h00 * self.ys[i]
+            + h10 * h * self.tangents[i]
+            + h01 * self.ys[i + 1]
+            + h11 * h * self.tangents[i + 1]
+    }
+}
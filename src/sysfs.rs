@@ -0,0 +1,81 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+// A single backlight device exposed under /sys/class/backlight/<name>.
+//
+// Brightness on sysfs backends is an integer in [0, max_brightness], not a
+// percentage, so every read/write has to rescale against `max_brightness`.
+pub struct SysfsDevice {
+    pub name: String,
+    path: PathBuf,
+    pub max_brightness: u32,
+}
+
+impl SysfsDevice {
+    const BACKLIGHT_ROOT: &'static str = "/sys/class/backlight";
+
+    // Enumerate every backlight device under /sys/class/backlight.
+    //
+    // Devices that can't be read (missing max_brightness, permission
+    // issues, etc.) are silently skipped rather than aborting the whole
+    // scan, since one broken entry shouldn't hide the others.
+    pub fn enumerate() -> Vec<SysfsDevice> {
+        let mut devices = Vec::new();
+
+        let entries = match fs::read_dir(Self::BACKLIGHT_ROOT) {
+            Ok(entries) => entries,
+            Err(_) => return devices,
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            let max_brightness = match fs::read_to_string(path.join("max_brightness")) {
+                Ok(contents) => match contents.trim().parse::<u32>() {
+                    Ok(value) => value,
+                    Err(_) => continue,
+                },
+                Err(_) => continue,
+            };
+
+            devices.push(SysfsDevice {
+                name,
+                path,
+                max_brightness,
+            });
+        }
+
+        devices
+    }
+
+    // Read the current brightness as a 0-100 percentage.
+    pub fn get_percent(&self) -> io::Result<u16> {
+        let raw = self.read_raw()?;
+        Ok(scale(raw, self.max_brightness, 100) as u16)
+    }
+
+    // Write a 0-100 percentage, scaled to this device's raw range.
+    pub fn set_percent(&self, pct: u16) -> io::Result<()> {
+        let raw = scale(pct as u32, 100, self.max_brightness);
+        fs::write(self.path.join("brightness"), raw.to_string())
+    }
+
+    fn read_raw(&self) -> io::Result<u32> {
+        let contents = fs::read_to_string(self.path.join("brightness"))?;
+        contents
+            .trim()
+            .parse::<u32>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+// Rescale `value` from the range [0, from_max] to [0, to_max], rounding to
+// the nearest integer.
+fn scale(value: u32, from_max: u32, to_max: u32) -> u32 {
+    if from_max == 0 {
+        return 0;
+    }
+    ((value as f64) * (to_max as f64) / (from_max as f64)).round() as u32
+}